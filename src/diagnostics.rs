@@ -0,0 +1,228 @@
+//! Diagnostics mode for the plugin runner.
+//!
+//! Reported failures like `InvalidAddressException: Offset outside of the
+//! buffer boundaries` or automagic silently failing to locate the kernel
+//! are normally only visible to users running `vol.py -vvvv` directly.
+//! This module installs a Python `logging` handler that bridges
+//! Volatility3's log records (including its fine-grained levels below
+//! `DEBUG`, used for that extra verbosity) into the `log` crate, and
+//! returns a [`DiagnosticReport`] summarizing what automagic and
+//! requirement validation actually did, so PowerShell callers get
+//! actionable detail without re-running anything.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use pyo3::prelude::*;
+use pyo3::types::{PyCFunction, PyDict, PyTuple};
+
+use crate::error::Result;
+use crate::row::Row;
+use crate::runner::{self, PluginConfig};
+
+/// Whether a plugin requirement was satisfied after automagic ran, and any
+/// detail Volatility3 reported about why it wasn't.
+#[derive(Debug, Clone)]
+pub struct RequirementStatus {
+    pub name: String,
+    pub satisfied: bool,
+    pub detail: Option<String>,
+}
+
+/// Snapshot of what the runner did while building and running a plugin:
+/// which automagics ran, whether the plugin's requirements ended up
+/// satisfied, the kernel offset (if any) automagic resolved, and the
+/// layer stack `LayerStacker` built on top of the raw image.
+#[derive(Debug, Clone, Default)]
+pub struct DiagnosticReport {
+    pub automagics_run: Vec<String>,
+    pub requirements: Vec<RequirementStatus>,
+    pub kernel_offset: Option<u64>,
+    pub layer_stack: Vec<String>,
+    /// `(description, percentage)` pairs reported through the progress
+    /// callback while automagic ran, in the order they were received.
+    /// `description` is `None` for calls Volatility3 makes with just a
+    /// percentage.
+    pub progress: Vec<(Option<String>, f64)>,
+}
+
+/// Runs `plugin_name` exactly like [`runner::run_plugin`], but with a
+/// logging bridge installed (see [`install_log_bridge`]) and a
+/// [`DiagnosticReport`] returned alongside the rows.
+pub fn run_plugin_diagnostic(
+    dump_path: &Path,
+    plugin_name: &str,
+    config: &PluginConfig,
+) -> Result<(Vec<Row>, DiagnosticReport)> {
+    Python::attach(|py| run_plugin_diagnostic_inner(py, dump_path, plugin_name, config))
+}
+
+fn run_plugin_diagnostic_inner(
+    py: Python<'_>,
+    dump_path: &Path,
+    plugin_name: &str,
+    config: &PluginConfig,
+) -> Result<(Vec<Row>, DiagnosticReport)> {
+    let _log_bridge = install_log_bridge(py)?;
+
+    let ctx = runner::new_context(py, dump_path)?;
+    let base_config_path = runner::plugin_config_path(plugin_name);
+    let ctx_config = ctx.getattr("config")?;
+    for (key, value) in &config.options {
+        ctx_config.set_item(format!("{base_config_path}.{key}"), value)?;
+    }
+
+    let plugin_class = runner::resolve_plugin_class(py, plugin_name, &config.plugin_dirs)?;
+
+    let progress = Arc::new(Mutex::new(Vec::new()));
+    let (plugin_instance, automagics_run) = runner::construct_plugin_with_progress(
+        py,
+        &ctx,
+        &plugin_class,
+        &base_config_path,
+        Some(Arc::clone(&progress)),
+    )?;
+
+    let requirements = requirement_statuses(&ctx, &plugin_class, &base_config_path)?;
+    let kernel_offset = kernel_offset(&ctx, &plugin_instance)?;
+    let layer_stack = layer_stack(&ctx)?;
+
+    let tree_grid = plugin_instance.call_method0("run")?;
+    let rows = runner::collect_rows(&tree_grid)?;
+
+    let report = DiagnosticReport {
+        automagics_run,
+        requirements,
+        kernel_offset,
+        layer_stack,
+        progress: Arc::try_unwrap(progress)
+            .expect("progress sink held elsewhere after construct_plugin_with_progress returns")
+            .into_inner()
+            .expect("progress sink mutex poisoned"),
+    };
+    Ok((rows, report))
+}
+
+/// Queries `plugin_class.get_requirements()` and checks each requirement's
+/// `unsatisfied(ctx, base_config_path)` result to report whether automagic
+/// left it satisfied, and why not when it didn't.
+fn requirement_statuses(
+    ctx: &Bound<'_, PyAny>,
+    plugin_class: &Bound<'_, PyAny>,
+    base_config_path: &str,
+) -> Result<Vec<RequirementStatus>> {
+    let requirements = plugin_class.call_method0("get_requirements")?;
+    let mut statuses = Vec::new();
+    for requirement in requirements.try_iter()? {
+        let requirement = requirement?;
+        let name: String = requirement.getattr("name")?.extract()?;
+        let unsatisfied = requirement.call_method1("unsatisfied", (ctx, base_config_path))?;
+        let satisfied = unsatisfied.len()? == 0;
+        let detail = if satisfied {
+            None
+        } else {
+            Some(unsatisfied.str()?.extract()?)
+        };
+        statuses.push(RequirementStatus { name, satisfied, detail });
+    }
+    Ok(statuses)
+}
+
+/// Reads the kernel virtual offset automagic resolved, by following the
+/// plugin's own `kernel` module requirement (the modern vol3 convention
+/// most OS-aware plugins use) into `ctx.modules` and reading the
+/// constructed module's `.offset` — rather than guessing at automagic's
+/// internal config keys, which aren't part of any stable contract.
+fn kernel_offset(ctx: &Bound<'_, PyAny>, plugin_instance: &Bound<'_, PyAny>) -> Result<Option<u64>> {
+    let py = ctx.py();
+    let plugin_config = plugin_instance.getattr("config")?;
+    let module_name = plugin_config.call_method1("get", ("kernel", py.None()))?;
+    if module_name.is_none() {
+        return Ok(None);
+    }
+    let module_name: String = module_name.extract()?;
+
+    let modules = ctx.getattr("modules")?;
+    let module = modules.call_method1("get", (&module_name, py.None()))?;
+    if module.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(module.getattr("offset")?.extract()?))
+}
+
+/// Lists the layer names `LayerStacker` built on top of the raw image,
+/// bottom-most layer first.
+fn layer_stack(ctx: &Bound<'_, PyAny>) -> Result<Vec<String>> {
+    ctx.getattr("layers")?
+        .try_iter()?
+        .map(|name| Ok(name?.extract()?))
+        .collect()
+}
+
+/// Handle for the logging bridge installed by [`install_log_bridge`].
+/// Removes the handler from volatility3's logger when dropped, so repeated
+/// diagnostic runs don't pile up duplicate handlers.
+struct LogBridgeGuard {
+    logger: Py<PyAny>,
+    handler: Py<PyAny>,
+}
+
+impl Drop for LogBridgeGuard {
+    fn drop(&mut self) {
+        Python::attach(|py| {
+            let _ = self.logger.bind(py).call_method1("removeHandler", (&self.handler,));
+        });
+    }
+}
+
+/// Installs a `logging.Handler`-shaped object on the `"volatility3"`
+/// logger that forwards every record to the `log` crate, mapping
+/// Volatility3's numeric levels (including the custom 1-9 range below
+/// `DEBUG`, used for `-vvvv`-and-beyond verbosity) onto [`log::Level`].
+/// Both the logger and the handler are dropped to level 1 so nothing is
+/// filtered out before it reaches the bridge; callers decide what to do
+/// with it via their own `log` backend.
+fn install_log_bridge(py: Python<'_>) -> Result<LogBridgeGuard> {
+    let logging = py.import("logging")?;
+    let logger = logging.call_method1("getLogger", ("volatility3",))?;
+    logger.call_method1("setLevel", (1,))?;
+
+    let handler = logging.getattr("Handler")?.call0()?;
+    handler.call_method1("setLevel", (1,))?;
+    handler.setattr(
+        "emit",
+        PyCFunction::new_closure(
+            py,
+            None,
+            None,
+            |args: &Bound<'_, PyTuple>, _kwargs: Option<&Bound<'_, PyDict>>| -> PyResult<Py<PyAny>> {
+                let record = args.get_item(0)?;
+                let levelno: i32 = record.getattr("levelno")?.extract()?;
+                let target: String = record.getattr("name")?.extract()?;
+                let message: String = record.call_method0("getMessage")?.extract()?;
+                log::log!(target: "volatility3", map_level(levelno), "[{target}] {message}");
+                Ok(args.py().None())
+            },
+        )?,
+    )?;
+    logger.call_method1("addHandler", (&handler,))?;
+
+    Ok(LogBridgeGuard {
+        logger: logger.unbind(),
+        handler: handler.unbind(),
+    })
+}
+
+/// Maps a Python `logging` level number onto a `log` crate level.
+/// Volatility3 registers extra levels from 1 (most verbose) through 9
+/// below the standard `DEBUG` (10), stepped through by repeating `-v`;
+/// all of those collapse onto [`log::Level::Trace`].
+fn map_level(levelno: i32) -> log::Level {
+    match levelno {
+        n if n >= 40 => log::Level::Error,
+        30..=39 => log::Level::Warn,
+        20..=29 => log::Level::Info,
+        10..=19 => log::Level::Debug,
+        _ => log::Level::Trace,
+    }
+}
@@ -0,0 +1,121 @@
+use std::fmt;
+
+/// Errors surfaced while driving the Volatility3 framework from Rust.
+#[derive(Debug)]
+pub enum Error {
+    /// A Python exception escaped from the embedded interpreter.
+    Python(pyo3::PyErr),
+    /// The requested plugin dotted path did not resolve to a loaded class.
+    PluginNotFound(String),
+    /// The installed Volatility3 build rejected our interface version check.
+    IncompatibleFramework(String),
+    /// A filesystem operation failed: opening or resolving the dump path,
+    /// writing a converted image or downloaded symbol pack, or flushing
+    /// rendered CSV.
+    Io(std::io::Error),
+    /// A required symbol table (e.g. the Windows kernel PDB/ISF pack)
+    /// could not be found locally or fetched from any configured mirror.
+    MissingSymbols {
+        /// The symbol table/banner identifier that was unsatisfied.
+        banner: String,
+        /// The configured ISF server URLs (`SymbolConfig::server_urls`).
+        /// `ensure_symbols` does not contact them; it fetches the kernel
+        /// PDB through Volatility3's `pdbconv` retriever.
+        candidates: Vec<String>,
+    },
+    /// CSV encoding of a result set failed.
+    Csv(csv::Error),
+    /// An unrecognized output format name was requested.
+    UnknownOutputFormat(String),
+    /// Importing plugins (built-in plus any `plugin_dirs`) raised
+    /// `argparse.ArgumentError` because two plugins tried to register a
+    /// command-line/config option under the same name. Volatility3 doesn't
+    /// merge these, it raises, so we surface it as a typed error rather
+    /// than letting the raw traceback escape. Any other exception raised
+    /// during the import is reported as [`Error::Python`].
+    PluginConfigConflict(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Python(err) => write!(f, "volatility3 raised a Python exception: {err}"),
+            Error::PluginNotFound(name) => write!(f, "no loaded plugin class matches `{name}`"),
+            Error::IncompatibleFramework(msg) => {
+                write!(f, "incompatible volatility3 framework: {msg}")
+            }
+            Error::Io(err) => write!(f, "I/O error: {err}"),
+            Error::MissingSymbols { banner, candidates } => write!(
+                f,
+                "missing symbol table `{banner}`; tried {} mirror(s): {}",
+                candidates.len(),
+                candidates.join(", ")
+            ),
+            Error::Csv(err) => write!(f, "failed to render CSV: {err}"),
+            Error::UnknownOutputFormat(name) => write!(f, "unknown output format `{name}`"),
+            Error::PluginConfigConflict(msg) => {
+                write!(f, "conflicting plugin command-line/config option: {msg}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<pyo3::PyErr> for Error {
+    fn from(err: pyo3::PyErr) -> Self {
+        Error::Python(err)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<csv::Error> for Error {
+    fn from(err: csv::Error) -> Self {
+        Error::Csv(err)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn displays_plugin_not_found() {
+        let err = Error::PluginNotFound("windows.pslist.PsList".to_string());
+        assert_eq!(err.to_string(), "no loaded plugin class matches `windows.pslist.PsList`");
+    }
+
+    #[test]
+    fn displays_unknown_output_format() {
+        let err = Error::UnknownOutputFormat("xml".to_string());
+        assert_eq!(err.to_string(), "unknown output format `xml`");
+    }
+
+    #[test]
+    fn displays_missing_symbols_with_candidate_count() {
+        let err = Error::MissingSymbols {
+            banner: "ntkrnlmp.pdb/3844DBB920174967BE7AA4A96E1C3317E".to_string(),
+            candidates: vec!["https://isf-server.czak.pl".to_string()],
+        };
+        assert_eq!(
+            err.to_string(),
+            "missing symbol table `ntkrnlmp.pdb/3844DBB920174967BE7AA4A96E1C3317E`; tried 1 mirror(s): https://isf-server.czak.pl"
+        );
+    }
+
+    #[test]
+    fn displays_plugin_config_conflict() {
+        let err = Error::PluginConfigConflict("conflicting option string: --pid".to_string());
+        assert_eq!(
+            err.to_string(),
+            "conflicting plugin command-line/config option: conflicting option string: --pid"
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! Format detection and raw-image conversion for the dump formats
+//! analysts actually hand us: raw images, Windows crash dumps,
+//! hibernation files, LiME captures, and VMware snapshots.
+//!
+//! Volatility3's own `LayerStacker` automagic already sniffs most of
+//! these from the `file://` location set in [`crate::runner::new_context`],
+//! so detection here exists to fail fast with a clear error (rather than
+//! a Python traceback). [`convert_to_raw`] does not use the detected
+//! format; automagic chooses the layers it reads.
+
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use pyo3::prelude::*;
+
+use crate::error::Result;
+use crate::runner;
+
+/// A recognized memory-image container format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// A flat physical-memory image (dd-style, or LiME's own raw segments).
+    Raw,
+    /// 32-bit Windows crash dump (`PAGEDUMP` signature).
+    WindowsCrashDump32,
+    /// 64-bit Windows crash dump (`PAGEDU64` signature).
+    WindowsCrashDump64,
+    /// Windows hibernation file (`hibr`/`HIBR` signature).
+    Hibernation,
+    /// LiME capture (`EMiL` magic, Linux Memory Extractor's on-disk byte order).
+    Lime,
+    /// VMware suspend-to-disk snapshot (`.vmem` backed by a sibling `.vmss`/`.vmsn`).
+    VmwareSnapshot,
+}
+
+const WINDOWS_CRASH_DUMP_32: &[u8; 8] = b"PAGEDUMP";
+const WINDOWS_CRASH_DUMP_64: &[u8; 8] = b"PAGEDU64";
+const HIBERNATION_MAGICS: [&[u8; 4]; 2] = [b"hibr", b"HIBR"];
+/// LiME's magic number (`0x4c694d45`) as it appears on disk, little-endian.
+const LIME_MAGIC: &[u8; 4] = b"EMiL";
+
+/// Volatility3's own `imagecopy`-equivalent plugin. Used purely as a
+/// vehicle to drive automagic (`LayerStacker` et al.) into building the
+/// `primary` translation layer, the same way `symbols::CANARY_PLUGIN`
+/// drives automagic to exercise symbol resolution.
+const PRIMARY_LAYER_PLUGIN: &str = "layerwriter.LayerWriter";
+
+/// Detects the on-disk format of `dump_path` from its header (and, for
+/// VMware, a sibling `.vmss`/`.vmsn` file), defaulting to [`ImageFormat::Raw`]
+/// when nothing more specific matches.
+pub fn detect_format(dump_path: &Path) -> Result<ImageFormat> {
+    let mut header = [0u8; 8];
+    let read = File::open(dump_path)?.read(&mut header)?;
+    let header = &header[..read];
+
+    if header.len() >= 8 && &header[..8] == WINDOWS_CRASH_DUMP_32 {
+        return Ok(ImageFormat::WindowsCrashDump32);
+    }
+    if header.len() >= 8 && &header[..8] == WINDOWS_CRASH_DUMP_64 {
+        return Ok(ImageFormat::WindowsCrashDump64);
+    }
+    if header.len() >= 4 && HIBERNATION_MAGICS.iter().any(|magic| &header[..4] == *magic) {
+        return Ok(ImageFormat::Hibernation);
+    }
+    if header.len() >= 4 && &header[..4] == LIME_MAGIC {
+        return Ok(ImageFormat::Lime);
+    }
+    if has_vmware_sibling(dump_path) {
+        return Ok(ImageFormat::VmwareSnapshot);
+    }
+
+    Ok(ImageFormat::Raw)
+}
+
+fn has_vmware_sibling(dump_path: &Path) -> bool {
+    if dump_path.extension().and_then(|ext| ext.to_str()) != Some("vmem") {
+        return false;
+    }
+    ["vmss", "vmsn"]
+        .iter()
+        .any(|ext| dump_path.with_extension(ext).exists())
+}
+
+/// Materializes a flat raw image from `dump_path` (the `imagecopy`
+/// equivalent), reporting progress in `[0.0, 100.0]` through `progress`.
+///
+/// Works for any format [`detect_format`] recognizes: Volatility3's
+/// automagic stacks whatever translation layers the format needs, and
+/// this then streams the resulting primary layer out byte for byte.
+pub fn convert_to_raw(
+    dump_path: &Path,
+    out_path: &Path,
+    mut progress: impl FnMut(f32),
+) -> Result<()> {
+    Python::attach(|py| convert_to_raw_inner(py, dump_path, out_path, &mut progress))
+}
+
+fn convert_to_raw_inner(
+    py: Python<'_>,
+    dump_path: &Path,
+    out_path: &Path,
+    progress: &mut dyn FnMut(f32),
+) -> Result<()> {
+    let ctx = runner::new_context(py, dump_path)?;
+
+    // Run automagic (via the real `layerwriter.LayerWriter` plugin, which
+    // requires nothing but a `primary` translation layer) so `ctx.layers`
+    // ends up with the fully stacked layer, same as the plugin runner does.
+    let plugin_class = runner::resolve_plugin_class(py, PRIMARY_LAYER_PLUGIN, &[])?;
+    let base_config_path = runner::plugin_config_path(PRIMARY_LAYER_PLUGIN);
+    runner::construct_plugin(py, &ctx, &plugin_class, &base_config_path)?;
+
+    // `imagecopy` wants a flat physical image, not the top-most virtual
+    // address space automagic built as `primary` (whose `maximum_address`
+    // is the ~2^48 virtual range, not the image's real size, and whose
+    // unmapped gaps would fail `read`). The bottom-most layer `ctx.layers`
+    // yields is the raw physical layer every translation layer stacks on
+    // top of -- the same base layer `symbols::scan_for_pdb_identity` reads
+    // the kernel PDB's CodeView record from.
+    let layers = ctx.getattr("layers")?;
+    let layer_name: String = layers
+        .try_iter()?
+        .next()
+        .expect("automagic must stack at least one layer to satisfy `primary`")?
+        .extract()?;
+    let layer = layers.get_item(&layer_name)?;
+    let size: u64 = layer.getattr("maximum_address")?.extract::<u64>()? + 1;
+
+    let mut out = File::create(out_path)?;
+    const CHUNK: u64 = 32 * 1024 * 1024;
+    let mut offset = 0u64;
+    while offset < size {
+        let chunk_len = CHUNK.min(size - offset);
+        let data: Vec<u8> = layer.call_method1("read", (offset, chunk_len))?.extract()?;
+        out.write_all(&data)?;
+        offset += chunk_len;
+        progress((offset as f64 / size as f64 * 100.0) as f32);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_temp(name: &str, bytes: &[u8]) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("memory_analysis_format_test_{}_{name}", std::process::id()));
+        File::create(&path).unwrap().write_all(bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn detects_windows_crash_dump_32() {
+        let path = write_temp("pagedump32", b"PAGEDUMP\0\0\0\0");
+        assert_eq!(detect_format(&path).unwrap(), ImageFormat::WindowsCrashDump32);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn detects_windows_crash_dump_64() {
+        let path = write_temp("pagedump64", b"PAGEDU64\0\0\0\0");
+        assert_eq!(detect_format(&path).unwrap(), ImageFormat::WindowsCrashDump64);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn detects_hibernation_file() {
+        let path = write_temp("hibr", b"hibr\0\0\0\0");
+        assert_eq!(detect_format(&path).unwrap(), ImageFormat::Hibernation);
+        std::fs::remove_file(path).unwrap();
+
+        let path = write_temp("hibr_upper", b"HIBR\0\0\0\0");
+        assert_eq!(detect_format(&path).unwrap(), ImageFormat::Hibernation);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn detects_lime_capture() {
+        let path = write_temp("lime", b"EMiL\0\0\0\0");
+        assert_eq!(detect_format(&path).unwrap(), ImageFormat::Lime);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn falls_back_to_raw_for_unrecognized_header() {
+        let path = write_temp("raw", b"\0\0\0\0\0\0\0\0");
+        assert_eq!(detect_format(&path).unwrap(), ImageFormat::Raw);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn falls_back_to_raw_for_short_file() {
+        let path = write_temp("short", b"PA");
+        assert_eq!(detect_format(&path).unwrap(), ImageFormat::Raw);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn detects_vmware_snapshot_from_sibling_file() {
+        let path = write_temp("snapshot.vmem", b"\0\0\0\0\0\0\0\0");
+        let vmss = path.with_extension("vmss");
+        File::create(&vmss).unwrap();
+
+        assert_eq!(detect_format(&path).unwrap(), ImageFormat::VmwareSnapshot);
+
+        std::fs::remove_file(path).unwrap();
+        std::fs::remove_file(vmss).unwrap();
+    }
+}
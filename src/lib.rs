@@ -0,0 +1,21 @@
+//! Rust core for MemoryAnalysis.Powershell.
+//!
+//! Drives the Volatility3 framework as an embedded Python library (via
+//! `pyo3`) so PowerShell cmdlets can run memory-forensics plugins without
+//! shelling out to `vol.py`.
+
+pub mod diagnostics;
+pub mod error;
+pub mod format;
+pub mod render;
+pub mod row;
+pub mod runner;
+pub mod symbols;
+
+pub use diagnostics::{run_plugin_diagnostic, DiagnosticReport, RequirementStatus};
+pub use error::{Error, Result};
+pub use format::{convert_to_raw, detect_format, ImageFormat};
+pub use render::{render, OutputFormat};
+pub use row::{CellValue, Row};
+pub use runner::{run_plugin, PluginConfig};
+pub use symbols::{ensure_symbols, SymbolConfig};
@@ -0,0 +1,211 @@
+//! Renders [`Row`]s (already flattened out of a Volatility3 `TreeGrid`
+//! by [`crate::runner::run_plugin`]) into the structured formats
+//! PowerShell cmdlets select between: JSON objects, CSV, and an aligned
+//! pretty table matching `vol.py -r pretty`.
+
+use std::fmt::Write as _;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::row::Row;
+
+/// Leading column every rendered format carries, preserving the
+/// `TreeGrid`'s parent/child nesting depth.
+const DEPTH_COLUMN: &str = "depth";
+
+/// Output format selectable from PowerShell, by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Json,
+    Csv,
+    Pretty,
+}
+
+impl FromStr for OutputFormat {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            "pretty" => Ok(OutputFormat::Pretty),
+            other => Err(Error::UnknownOutputFormat(other.to_string())),
+        }
+    }
+}
+
+/// Renders `rows` in the given format.
+pub fn render(rows: &[Row], format: OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Json => Ok(to_json(rows).to_string()),
+        OutputFormat::Csv => to_csv(rows),
+        OutputFormat::Pretty => Ok(to_pretty(rows)),
+    }
+}
+
+/// Renders `rows` as a JSON array, one object per row, with a `depth`
+/// field alongside the plugin's own columns.
+pub fn to_json(rows: &[Row]) -> serde_json::Value {
+    serde_json::Value::Array(rows.iter().map(row_to_json).collect())
+}
+
+fn row_to_json(row: &Row) -> serde_json::Value {
+    let mut object = serde_json::Map::with_capacity(row.values.len() + 1);
+    object.insert(DEPTH_COLUMN.to_string(), serde_json::Value::from(row.depth as u64));
+    for (name, value) in &row.values {
+        object.insert(name.clone(), value.to_json());
+    }
+    serde_json::Value::Object(object)
+}
+
+/// Renders `rows` as CSV text, with a leading `depth` column.
+pub fn to_csv(rows: &[Row]) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+
+    if let Some(first) = rows.first() {
+        let mut header = vec![DEPTH_COLUMN.to_string()];
+        header.extend(first.values.iter().map(|(name, _)| name.clone()));
+        writer.write_record(&header)?;
+    }
+
+    for row in rows {
+        let mut record = vec![row.depth.to_string()];
+        record.extend(row.values.iter().map(|(_, value)| value.to_display()));
+        writer.write_record(&record)?;
+    }
+
+    let bytes = writer.into_inner().map_err(|err| Error::Io(err.into_error()))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Renders `rows` as a pretty-aligned table, indenting the first plugin
+/// column (the one after the leading `depth` column) per row by its tree
+/// depth (matching `vol.py -r pretty`).
+pub fn to_pretty(rows: &[Row]) -> String {
+    let Some(first) = rows.first() else {
+        return String::new();
+    };
+
+    let headers: Vec<String> = std::iter::once(DEPTH_COLUMN.to_string())
+        .chain(first.values.iter().map(|(name, _)| name.clone()))
+        .collect();
+
+    let records: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            let mut fields = vec![row.depth.to_string()];
+            fields.extend(row.values.iter().enumerate().map(|(i, (_, value))| {
+                let field = value.to_display();
+                if i == 0 {
+                    format!("{}{field}", "  ".repeat(row.depth))
+                } else {
+                    field
+                }
+            }));
+            fields
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+    for record in &records {
+        for (width, field) in widths.iter_mut().zip(record) {
+            *width = (*width).max(field.len());
+        }
+    }
+
+    let mut out = String::new();
+    write_pretty_row(&mut out, &headers, &widths);
+    writeln!(out, "{}", widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>().join("-+-"))
+        .expect("writing to a String cannot fail");
+    for record in &records {
+        write_pretty_row(&mut out, record, &widths);
+    }
+    out
+}
+
+fn write_pretty_row(out: &mut String, fields: &[String], widths: &[usize]) {
+    let cells: Vec<String> = fields
+        .iter()
+        .zip(widths)
+        .map(|(field, width)| format!("{field:width$}"))
+        .collect();
+    writeln!(out, "{}", cells.join(" | ")).expect("writing to a String cannot fail");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::row::CellValue;
+
+    fn sample_rows() -> Vec<Row> {
+        vec![
+            Row {
+                depth: 0,
+                values: vec![
+                    ("PID".to_string(), CellValue::Int(4)),
+                    ("ImageFileName".to_string(), CellValue::Str("System".to_string())),
+                ],
+            },
+            Row {
+                depth: 1,
+                values: vec![
+                    ("PID".to_string(), CellValue::Int(88)),
+                    ("ImageFileName".to_string(), CellValue::Str("svchost.exe".to_string())),
+                ],
+            },
+        ]
+    }
+
+    #[test]
+    fn output_format_parses_case_insensitively() {
+        assert_eq!(OutputFormat::from_str("JSON").unwrap(), OutputFormat::Json);
+        assert_eq!(OutputFormat::from_str("csv").unwrap(), OutputFormat::Csv);
+        assert_eq!(OutputFormat::from_str("Pretty").unwrap(), OutputFormat::Pretty);
+    }
+
+    #[test]
+    fn output_format_rejects_unknown_names() {
+        let err = OutputFormat::from_str("xml").unwrap_err();
+        assert!(matches!(err, Error::UnknownOutputFormat(name) if name == "xml"));
+    }
+
+    #[test]
+    fn to_json_includes_depth_alongside_columns() {
+        let rows = sample_rows();
+        let json = to_json(&rows);
+        let first = &json[0];
+        assert_eq!(first[DEPTH_COLUMN], serde_json::json!(0));
+        assert_eq!(first["PID"], serde_json::json!(4));
+        assert_eq!(first["ImageFileName"], serde_json::json!("System"));
+    }
+
+    #[test]
+    fn to_csv_writes_header_from_first_row() {
+        let rows = sample_rows();
+        let csv = to_csv(&rows).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("depth,PID,ImageFileName"));
+        assert_eq!(lines.next(), Some("0,4,System"));
+        assert_eq!(lines.next(), Some("1,88,svchost.exe"));
+    }
+
+    #[test]
+    fn to_csv_empty_rows_produces_empty_output() {
+        assert_eq!(to_csv(&[]).unwrap(), "");
+    }
+
+    #[test]
+    fn to_pretty_empty_rows_produces_empty_string() {
+        assert_eq!(to_pretty(&[]), "");
+    }
+
+    #[test]
+    fn to_pretty_indents_first_column_by_depth() {
+        let rows = sample_rows();
+        let pretty = to_pretty(&rows);
+        let lines: Vec<&str> = pretty.lines().collect();
+        assert_eq!(lines[0], "depth | PID  | ImageFileName");
+        assert_eq!(lines[2], "0     | 4    | System       ");
+        assert_eq!(lines[3], "1     |   88 | svchost.exe  ");
+    }
+}
@@ -0,0 +1,134 @@
+use pyo3::prelude::*;
+
+/// A single value extracted from a `TreeGrid` cell.
+///
+/// Volatility3 plugins emit a handful of Python types (ints, floats, str,
+/// bytes, bools, `None`); this is the Rust-side normalization of those.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellValue {
+    Int(i64),
+    /// Unsigned values that don't fit in `i64`, namely `format_hints.Hex`
+    /// kernel addresses (e.g. `0xFFFFF80000000000`), which are common
+    /// enough in Windows plugin output that they need an exact, lossless
+    /// representation rather than falling through to `Float`.
+    UInt(u64),
+    Float(f64),
+    Str(String),
+    Bool(bool),
+    Bytes(Vec<u8>),
+    None,
+}
+
+impl CellValue {
+    pub(crate) fn from_py(value: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if value.is_none() {
+            Ok(CellValue::None)
+        } else if let Ok(b) = value.extract::<bool>() {
+            Ok(CellValue::Bool(b))
+        } else if let Ok(i) = value.extract::<i64>() {
+            Ok(CellValue::Int(i))
+        } else if let Ok(u) = value.extract::<u64>() {
+            Ok(CellValue::UInt(u))
+        } else if let Ok(f) = value.extract::<f64>() {
+            Ok(CellValue::Float(f))
+        } else if let Ok(bytes) = value.extract::<Vec<u8>>() {
+            Ok(CellValue::Bytes(bytes))
+        } else {
+            Ok(CellValue::Str(value.str()?.to_string()))
+        }
+    }
+
+    /// Renders the value the way a CSV/pretty-table cell would: plain
+    /// text, with bytes hex-encoded since both formats are text-only.
+    pub fn to_display(&self) -> String {
+        match self {
+            CellValue::Int(i) => i.to_string(),
+            CellValue::UInt(u) => u.to_string(),
+            CellValue::Float(f) => f.to_string(),
+            CellValue::Str(s) => s.clone(),
+            CellValue::Bool(b) => b.to_string(),
+            CellValue::Bytes(bytes) => hex_encode(bytes),
+            CellValue::None => String::new(),
+        }
+    }
+
+    /// Converts to a `serde_json::Value`, hex-encoding bytes (JSON has no
+    /// binary type) and falling back to `null` for non-finite floats.
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            CellValue::Int(i) => serde_json::Value::from(*i),
+            CellValue::UInt(u) => serde_json::Value::from(*u),
+            CellValue::Float(f) => serde_json::Number::from_f64(*f)
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+            CellValue::Str(s) => serde_json::Value::from(s.clone()),
+            CellValue::Bool(b) => serde_json::Value::from(*b),
+            CellValue::Bytes(bytes) => serde_json::Value::from(hex_encode(bytes)),
+            CellValue::None => serde_json::Value::Null,
+        }
+    }
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+    bytes.iter().fold(String::with_capacity(bytes.len() * 2), |mut out, byte| {
+        let _ = write!(out, "{byte:02x}");
+        out
+    })
+}
+
+/// One row of a flattened `TreeGrid`, as produced by [`crate::runner::run_plugin`].
+///
+/// `depth` preserves the tree's parent/child nesting (0 = root row) so a
+/// renderer can reconstruct indentation or hierarchy without re-walking
+/// Python objects.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    pub depth: usize,
+    pub values: Vec<(String, CellValue)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_encode_pads_each_byte() {
+        assert_eq!(hex_encode(&[0x00, 0x0a, 0xff]), "000aff");
+        assert_eq!(hex_encode(&[]), "");
+    }
+
+    #[test]
+    fn to_display_renders_each_variant() {
+        assert_eq!(CellValue::Int(-5).to_display(), "-5");
+        assert_eq!(CellValue::UInt(0xFFFF_FFFF_FFFF_FFFF).to_display(), "18446744073709551615");
+        assert_eq!(CellValue::Float(1.5).to_display(), "1.5");
+        assert_eq!(CellValue::Str("hi".to_string()).to_display(), "hi");
+        assert_eq!(CellValue::Bool(true).to_display(), "true");
+        assert_eq!(CellValue::Bytes(vec![0xde, 0xad]).to_display(), "dead");
+        assert_eq!(CellValue::None.to_display(), "");
+    }
+
+    #[test]
+    fn to_json_preserves_large_unsigned_addresses() {
+        // The whole point of `UInt`: a kernel address like this overflows
+        // `i64` and would otherwise have round-tripped through `Float` and
+        // lost precision.
+        let address = CellValue::UInt(0xFFFF_F800_0000_0000);
+        assert_eq!(address.to_json(), serde_json::json!(0xFFFF_F800_0000_0000u64));
+    }
+
+    #[test]
+    fn to_json_nulls_non_finite_floats() {
+        assert_eq!(CellValue::Float(f64::NAN).to_json(), serde_json::Value::Null);
+        assert_eq!(CellValue::Float(f64::INFINITY).to_json(), serde_json::Value::Null);
+    }
+
+    #[test]
+    fn to_json_hex_encodes_bytes() {
+        assert_eq!(
+            CellValue::Bytes(vec![0x01, 0x02]).to_json(),
+            serde_json::Value::from("0102")
+        );
+    }
+}
@@ -0,0 +1,317 @@
+//! Drives Volatility3 as an embedded library rather than shelling out to
+//! `vol.py`, so any plugin the installed framework knows about can be run
+//! from PowerShell without per-plugin Rust glue.
+
+use std::path::{Path, PathBuf};
+
+use std::sync::{Arc, Mutex};
+
+use pyo3::prelude::*;
+use pyo3::types::{PyCFunction, PyDict, PyTuple};
+
+use crate::error::{Error, Result};
+use crate::row::{CellValue, Row};
+
+/// Interface version this crate was written against. Volatility3 rejects
+/// the call in `require_interface_version` if its own version is
+/// incompatible, which we surface as [`Error::IncompatibleFramework`].
+const INTERFACE_VERSION: (u8, u8, u8) = (2, 0, 0);
+
+/// Config path every plugin is constructed under.
+pub(crate) const BASE_CONFIG_PATH: &str = "plugins";
+
+/// `(description, percentage)` pairs reported through a progress callback,
+/// shared with the closure that's still writing to it. `description` is
+/// `None` for the (documented) calls Volatility3 makes with just a
+/// percentage.
+pub(crate) type ProgressSink = Arc<Mutex<Vec<(Option<String>, f64)>>>;
+
+/// Extra plugin-specific options, applied under the plugin's own config
+/// path before automagic runs. Keys are leaf option names (e.g. `"pid"`),
+/// not full dotted config paths.
+#[derive(Debug, Clone, Default)]
+pub struct PluginConfig {
+    pub options: Vec<(String, String)>,
+    /// Community/custom plugin directories, in priority order. Prepended
+    /// onto `volatility3.plugins.__path__` before plugin discovery runs,
+    /// mirroring `vol.py --plugins=PATH`, so plugins dropped in here are
+    /// importable by dotted name just like built-ins.
+    pub plugin_dirs: Vec<PathBuf>,
+}
+
+/// Runs a Volatility3 plugin against a memory dump and returns its
+/// `TreeGrid` output flattened into [`Row`]s.
+///
+/// `plugin_name` is the plugin's dotted path relative to
+/// `volatility3.plugins`, e.g. `"windows.pslist.PsList"` or
+/// `"windows.netscan.NetScan"`.
+pub fn run_plugin(dump_path: &Path, plugin_name: &str, config: &PluginConfig) -> Result<Vec<Row>> {
+    Python::attach(|py| run_plugin_inner(py, dump_path, plugin_name, config))
+}
+
+fn run_plugin_inner(
+    py: Python<'_>,
+    dump_path: &Path,
+    plugin_name: &str,
+    config: &PluginConfig,
+) -> Result<Vec<Row>> {
+    let ctx = new_context(py, dump_path)?;
+    let ctx_config = ctx.getattr("config")?;
+    // Isolated per plugin so a community plugin from `config.plugin_dirs`
+    // can't collide with a built-in (or another custom plugin) registering
+    // options under the same shared base path.
+    let base_config_path = plugin_config_path(plugin_name);
+    for (key, value) in &config.options {
+        ctx_config.set_item(format!("{base_config_path}.{key}"), value)?;
+    }
+
+    let plugin_class = resolve_plugin_class(py, plugin_name, &config.plugin_dirs)?;
+    let plugin_instance = construct_plugin(py, &ctx, &plugin_class, &base_config_path)?;
+
+    let tree_grid = plugin_instance.call_method0("run")?;
+    collect_rows(&tree_grid)
+}
+
+/// Per-plugin config base path, so two plugins never share the same
+/// config namespace. See [`PluginConfig::plugin_dirs`].
+pub(crate) fn plugin_config_path(plugin_name: &str) -> String {
+    format!("{BASE_CONFIG_PATH}.{}", plugin_name.replace(['.', ':'], "_"))
+}
+
+/// Builds a fresh Volatility3 [`Context`] pointed at `dump_path` via the
+/// `LayerStacker` automagic's single-location setting. Shared by the
+/// plugin runner and the symbol-table subsystem, which both need a
+/// context before they know which plugin (if any) they're driving.
+pub(crate) fn new_context<'py>(py: Python<'py>, dump_path: &Path) -> Result<Bound<'py, PyAny>> {
+    // Fail fast with a clear `Error::Io` (missing/unreadable file) rather
+    // than letting automagic surface a Python traceback later.
+    crate::format::detect_format(dump_path)?;
+
+    let framework = py.import("volatility3.framework")?;
+    framework
+        .call_method1("require_interface_version", INTERFACE_VERSION)
+        .map_err(|err| Error::IncompatibleFramework(err.to_string()))?;
+
+    let ctx = py
+        .import("volatility3.framework.contexts")?
+        .getattr("Context")?
+        .call0()?;
+    ctx.getattr("config")?.set_item(
+        "automagic.LayerStacker.single_location",
+        single_location_url(py, dump_path)?,
+    )?;
+    Ok(ctx)
+}
+
+/// Runs automagic for `plugin_class` against `ctx` and constructs it,
+/// returning the live plugin instance (before `.run()` is called).
+///
+/// `base_config_path` is the plugin's own isolated config path (see
+/// [`plugin_config_path`]), not the shared [`BASE_CONFIG_PATH`].
+pub(crate) fn construct_plugin<'py>(
+    py: Python<'py>,
+    ctx: &Bound<'py, PyAny>,
+    plugin_class: &Bound<'py, PyAny>,
+    base_config_path: &str,
+) -> Result<Bound<'py, PyAny>> {
+    construct_plugin_with_progress(py, ctx, plugin_class, base_config_path, None).map(|(instance, _)| instance)
+}
+
+/// Like [`construct_plugin`], but also reports the automagic classes that
+/// were chosen to run and, if `progress_sink` is given, every
+/// `(description, percentage)` pair the automagics reported through the
+/// progress callback while they ran. Used by [`crate::diagnostics`].
+pub(crate) fn construct_plugin_with_progress<'py>(
+    py: Python<'py>,
+    ctx: &Bound<'py, PyAny>,
+    plugin_class: &Bound<'py, PyAny>,
+    base_config_path: &str,
+    progress_sink: Option<ProgressSink>,
+) -> Result<(Bound<'py, PyAny>, Vec<String>)> {
+    let automagic_mod = py.import("volatility3.framework.automagic")?;
+    let available = automagic_mod.call_method1("available", (ctx,))?;
+    let chosen = automagic_mod.call_method1("choose_automagic", (available, plugin_class))?;
+
+    let chosen_names: Vec<String> = chosen
+        .try_iter()?
+        .map(|automagic| automagic?.getattr("__class__")?.getattr("__name__")?.extract())
+        .collect::<PyResult<_>>()?;
+
+    // `volatility3.cli` has no plain `FileHandler` class to import; it
+    // builds one on demand via `file_handler_class_factory`, which returns
+    // a `FileHandlerInterface` subclass that writes files constructed
+    // plugins open (e.g. `dumpfiles`) into the given output directory.
+    let file_handler = py
+        .import("volatility3.cli")?
+        .call_method1("file_handler_class_factory", (".",))?;
+    let progress_callback = match progress_sink {
+        Some(sink) => PyCFunction::new_closure(
+            py,
+            None,
+            None,
+            move |args: &Bound<'_, PyTuple>, _kwargs: Option<&Bound<'_, PyDict>>| -> PyResult<Py<PyAny>> {
+                let percentage: f64 = args.get_item(0)?.extract()?;
+                // Volatility3's progress callbacks are sometimes invoked
+                // with just a percentage, and the description argument
+                // itself may be `None`; don't assume either.
+                let description: Option<String> = match args.get_item(1) {
+                    Ok(value) if !value.is_none() => Some(value.extract()?),
+                    _ => None,
+                };
+                sink.lock()
+                    .expect("progress sink mutex poisoned")
+                    .push((description, percentage));
+                Ok(args.py().None())
+            },
+        )?,
+        None => PyCFunction::new_closure(
+            py,
+            None,
+            None,
+            |args: &Bound<'_, PyTuple>, _kwargs: Option<&Bound<'_, PyDict>>| -> PyResult<Py<PyAny>> {
+                Ok(args.py().None())
+            },
+        )?,
+    };
+
+    let instance = py.import("volatility3.framework.plugins")?.call_method1(
+        "construct_plugin",
+        (
+            ctx,
+            chosen,
+            plugin_class,
+            base_config_path,
+            progress_callback,
+            file_handler,
+        ),
+    )?;
+    Ok((instance, chosen_names))
+}
+
+/// Resolves a dotted plugin path (e.g. `"windows.pslist.PsList"`) to a
+/// loaded plugin class, importing every installed plugin module first
+/// (built-in plus anything under `plugin_dirs`) so community/custom
+/// plugins are visible too.
+pub(crate) fn resolve_plugin_class<'py>(
+    py: Python<'py>,
+    dotted: &str,
+    plugin_dirs: &[PathBuf],
+) -> Result<Bound<'py, PyAny>> {
+    let framework = py.import("volatility3.framework")?;
+    let plugins_package = py.import("volatility3.plugins")?;
+    extend_plugin_search_path(&plugins_package, plugin_dirs)?;
+
+    framework
+        .call_method1("import_files", (&plugins_package, false))
+        .map_err(|err| {
+            if is_duplicate_option_conflict(py, &err) {
+                Error::PluginConfigConflict(err.to_string())
+            } else {
+                Error::Python(err)
+            }
+        })?;
+
+    let plugin_interface = py
+        .import("volatility3.framework.interfaces.plugins")?
+        .getattr("PluginInterface")?;
+    let subclasses = framework.call_method1("class_subclasses", (plugin_interface,))?;
+
+    for class in subclasses.try_iter()? {
+        let class = class?;
+        let module: String = class.getattr("__module__")?.extract()?;
+        let qual_name: String = class.getattr("__qualname__")?.extract()?;
+        let short_module = module
+            .strip_prefix("volatility3.plugins.")
+            .unwrap_or(&module);
+        if format!("{short_module}.{qual_name}") == dotted {
+            return Ok(class);
+        }
+    }
+
+    Err(Error::PluginNotFound(dotted.to_string()))
+}
+
+/// True if `err` is `argparse.ArgumentError`, the exception Python's own
+/// argument parser raises when two plugins try to register the same
+/// command-line/config option name (`"conflicting option string"`). Any
+/// other failure (syntax error, missing dependency, ...) in a community
+/// plugin from `plugin_dirs` should surface as a plain [`Error::Python`],
+/// not be misreported as a config conflict.
+fn is_duplicate_option_conflict(py: Python<'_>, err: &pyo3::PyErr) -> bool {
+    py.import("argparse")
+        .and_then(|argparse| argparse.getattr("ArgumentError"))
+        .map(|argument_error| err.value(py).is_instance(&argument_error).unwrap_or(false))
+        .unwrap_or(false)
+}
+
+/// Prepends `plugin_dirs` onto `volatility3.plugins.__path__` (the
+/// namespace package search path `import_files` walks), highest-priority
+/// directory first, so the plugins they contain get discovered alongside
+/// the built-ins. A no-op if `plugin_dirs` is empty.
+fn extend_plugin_search_path(plugins_package: &Bound<'_, PyAny>, plugin_dirs: &[PathBuf]) -> Result<()> {
+    if plugin_dirs.is_empty() {
+        return Ok(());
+    }
+
+    let path_list = plugins_package.getattr("__path__")?;
+    for dir in plugin_dirs.iter().rev() {
+        let dir = dir.to_string_lossy().to_string();
+        if !path_list.contains(dir.as_str())? {
+            path_list.call_method1("insert", (0, &dir))?;
+        }
+    }
+    Ok(())
+}
+
+/// Builds the `file://`-style location Volatility3's `LayerStacker`
+/// automagic expects for `automagic.LayerStacker.single_location`.
+fn single_location_url(py: Python<'_>, dump_path: &Path) -> Result<String> {
+    let absolute = dump_path.canonicalize()?;
+    let encoded: String = py
+        .import("urllib.request")?
+        .call_method1("pathname2url", (absolute.to_string_lossy().to_string(),))?
+        .extract()?;
+    Ok(format!("file:{encoded}"))
+}
+
+/// Walks a `TreeGrid` via its `visit` method, flattening the tree into
+/// [`Row`]s while recording each node's depth.
+pub(crate) fn collect_rows(tree_grid: &Bound<'_, PyAny>) -> Result<Vec<Row>> {
+    let py = tree_grid.py();
+    let column_names: Vec<String> = tree_grid
+        .getattr("columns")?
+        .try_iter()?
+        .map(|column| column?.getattr("name")?.extract())
+        .collect::<PyResult<_>>()?;
+
+    let rows = Arc::new(Mutex::new(Vec::new()));
+    let rows_for_visitor = Arc::clone(&rows);
+    let column_names_for_visitor = column_names.clone();
+
+    let visitor = PyCFunction::new_closure(
+        py,
+        None,
+        None,
+        move |args: &Bound<'_, PyTuple>, _kwargs: Option<&Bound<'_, PyDict>>| -> PyResult<Py<PyAny>> {
+            let node = args.get_item(0)?;
+            let depth: usize = node.getattr("path_depth")?.extract()?;
+            let values = node.getattr("values")?;
+            let mut row_values = Vec::with_capacity(column_names_for_visitor.len());
+            for (name, value) in column_names_for_visitor.iter().zip(values.try_iter()?) {
+                row_values.push((name.clone(), CellValue::from_py(&value?)?));
+            }
+            rows_for_visitor
+                .lock()
+                .expect("row collector mutex poisoned")
+                .push(Row { depth, values: row_values });
+            Ok(args.py().None())
+        },
+    )?;
+
+    tree_grid.call_method1("visit", (py.None(), visitor, py.None()))?;
+
+    Ok(Arc::try_unwrap(rows)
+        .expect("visitor closure dropped after visit() returns")
+        .into_inner()
+        .expect("row collector mutex poisoned"))
+}
@@ -0,0 +1,322 @@
+//! Symbol-table management: detects the "symbol requirement was not
+//! fulfilled" failure that automagic otherwise reports as a raw Python
+//! traceback, fetches the missing Windows kernel PDB through Volatility3's
+//! `pdbconv` retriever, converts it to an ISF pack and retries.
+
+use std::path::{Path, PathBuf};
+
+use pyo3::prelude::*;
+
+use crate::error::{Error, Result};
+use crate::runner;
+
+/// Plugin used purely as a vehicle to exercise automagic and symbol
+/// resolution; any plugin requiring `nt_symbols` would do just as well.
+const CANARY_PLUGIN: &str = "windows.pslist.PsList";
+
+const DEFAULT_SERVER_URLS: &[&str] = &[
+    "https://isf-server.czak.pl",
+    "https://volatility3.s3.amazonaws.com/symbols",
+];
+
+/// Where this crate looks for (and downloads) Windows ISF symbol packs.
+#[derive(Debug, Clone)]
+pub struct SymbolConfig {
+    /// Local directories searched for ISF packs, in priority order. The
+    /// last entry is treated as the writable cache a download lands in.
+    pub symbol_paths: Vec<PathBuf>,
+    /// ISF symbol server URLs, reported as the `candidates` of
+    /// [`Error::MissingSymbols`]. They are not contacted: a missing kernel
+    /// pack is fetched as a PDB through Volatility3's `pdbconv` retriever.
+    pub server_urls: Vec<String>,
+}
+
+impl Default for SymbolConfig {
+    /// Mirrors Volatility3's own defaults: the bundled `volatility3/symbols`
+    /// directory and the framework's cache directory, falling back to an
+    /// empty symbol path list if the framework can't be introspected yet.
+    fn default() -> Self {
+        let symbol_paths = Python::attach(default_symbol_paths).unwrap_or_default();
+        SymbolConfig {
+            symbol_paths,
+            server_urls: DEFAULT_SERVER_URLS.iter().map(|url| url.to_string()).collect(),
+        }
+    }
+}
+
+fn default_symbol_paths(py: Python<'_>) -> Result<Vec<PathBuf>> {
+    let package_file: String = py.import("volatility3")?.getattr("__file__")?.extract()?;
+    let package_dir = Path::new(&package_file)
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+
+    let cache_path: String = py
+        .import("volatility3.framework.constants")?
+        .getattr("CACHE_PATH")?
+        .extract()?;
+
+    Ok(vec![package_dir.join("symbols"), PathBuf::from(cache_path)])
+}
+
+/// Ensures the Windows kernel symbol table needed to analyze `dump_path`
+/// is present, searching `config.symbol_paths` ahead of Volatility3's own
+/// symbol directories and, if automagic can't find it locally, downloading
+/// the kernel PDB through `pdbconv` and caching the converted pack in the
+/// last entry of `config.symbol_paths`.
+pub fn ensure_symbols(dump_path: &Path, config: &SymbolConfig) -> Result<()> {
+    Python::attach(|py| ensure_symbols_inner(py, dump_path, config))
+}
+
+fn ensure_symbols_inner(py: Python<'_>, dump_path: &Path, config: &SymbolConfig) -> Result<()> {
+    // Prepend `config.symbol_paths` onto `volatility3.symbols.__path__`,
+    // highest-priority directory first, so automagic searches them.
+    let symbol_search_path = py.import("volatility3.symbols")?.getattr("__path__")?;
+    for dir in config.symbol_paths.iter().rev() {
+        let dir = dir.to_string_lossy().to_string();
+        if !symbol_search_path.contains(dir.as_str())? {
+            symbol_search_path.call_method1("insert", (0, &dir))?;
+        }
+    }
+
+    let ctx = runner::new_context(py, dump_path)?;
+    let plugin_class = runner::resolve_plugin_class(py, CANARY_PLUGIN, &[])?;
+    let base_config_path = runner::plugin_config_path(CANARY_PLUGIN);
+
+    match runner::construct_plugin(py, &ctx, &plugin_class, &base_config_path) {
+        Ok(_) => Ok(()),
+        Err(Error::Python(err)) => {
+            if !is_missing_symbols(py, &err)? {
+                return Err(Error::Python(err));
+            }
+            // `UnsatisfiedException` only names the unfulfilled requirement
+            // (e.g. `nt_symbols`), not the PDB; automagic already stacked
+            // the image's layers by this point, so recover the kernel's
+            // PDB identity ourselves from the bottom (physical) layer.
+            let Some(pdb) = scan_for_pdb_identity(&ctx)? else {
+                return Err(Error::Python(err));
+            };
+            download_symbol_pack(py, &pdb, config)?;
+            runner::construct_plugin(py, &ctx, &plugin_class, &base_config_path)
+                .map(|_| ())
+                .map_err(|_| Error::MissingSymbols {
+                    banner: pdb.banner(),
+                    candidates: config.server_urls.clone(),
+                })
+        }
+        Err(other) => Err(other),
+    }
+}
+
+/// True if `err` is Volatility3's `exceptions.UnsatisfiedException` (raised
+/// by `construct_plugin` when a requirement is left unfulfilled after
+/// automagic runs) and at least one of the requirement paths in its
+/// `.unsatisfied` dict is a symbol table (e.g. `nt_symbols`). `SymbolError`
+/// (raised mid-automagic, not at final validation) doesn't carry a `.name`
+/// we could use here, so we don't match on it.
+fn is_missing_symbols(py: Python<'_>, err: &pyo3::PyErr) -> Result<bool> {
+    let Ok(unsatisfied_exc) = py.import("volatility3.framework.exceptions")?.getattr("UnsatisfiedException") else {
+        return Ok(false);
+    };
+    let value = err.value(py);
+    if !value.is_instance(&unsatisfied_exc)? {
+        return Ok(false);
+    }
+
+    let unsatisfied = value.getattr("unsatisfied")?;
+    for key in unsatisfied.try_iter()? {
+        let key: String = key?.extract()?;
+        if key.contains("symbols") {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// A Windows kernel PDB's CodeView identity (name, GUID, age), as embedded
+/// in its PE debug directory and parsed out of the raw image by
+/// [`parse_pdb70_record`].
+#[derive(Debug, Clone, PartialEq)]
+struct PdbIdentity {
+    name: String,
+    /// 16-byte GUID, formatted as uppercase hex with no separators.
+    guid_hex: String,
+    age: u32,
+}
+
+impl PdbIdentity {
+    /// `<pdb-name>/<GUID><AGE>`, the same string Microsoft-compatible
+    /// symbol servers use as the directory component identifying this
+    /// exact PDB, and what we report in [`Error::MissingSymbols`].
+    fn banner(&self) -> String {
+        format!("{}/{}{:X}", self.name, self.guid_hex, self.age)
+    }
+}
+
+/// Scans the bottom-most (physical) layer of `ctx.layers` for the first
+/// PDB70 CodeView debug-info record (`RSDS` signature, see
+/// [`parse_pdb70_record`]) identifying the kernel's own PDB.
+fn scan_for_pdb_identity(ctx: &Bound<'_, PyAny>) -> Result<Option<PdbIdentity>> {
+    let layers = ctx.getattr("layers")?;
+    let Some(layer_name) = layers.try_iter()?.next() else {
+        return Ok(None);
+    };
+    let layer_name: String = layer_name?.extract()?;
+    let layer = layers.get_item(&layer_name)?;
+    let size: u64 = layer.getattr("maximum_address")?.extract::<u64>()? + 1;
+
+    const CHUNK: u64 = 16 * 1024 * 1024;
+    // The kernel's PE header (and its embedded PDB70 record) loads well
+    // within the first few hundred MiB of physical memory in practice.
+    const SCAN_LIMIT: u64 = 256 * 1024 * 1024;
+    let mut offset = 0u64;
+    while offset < size.min(SCAN_LIMIT) {
+        let chunk_len = CHUNK.min(size - offset);
+        let data: Vec<u8> = layer.call_method1("read", (offset, chunk_len))?.extract()?;
+        if let Some(pdb) = parse_pdb70_record(&data) {
+            return Ok(Some(pdb));
+        }
+        offset += chunk_len;
+    }
+    Ok(None)
+}
+
+/// Signature preceding a PDB70 CodeView debug-info record (`RSDS` | 16-byte
+/// GUID | `u32` age, little-endian | NUL-terminated ASCII PDB name), the
+/// same record Volatility3's own Windows PDB-scanning automagic reads to
+/// identify the kernel's symbol table.
+const PDB70_SIGNATURE: &[u8; 4] = b"RSDS";
+
+/// Parses the first PDB70 record in `data` into a [`PdbIdentity`], or
+/// `None` if `data` doesn't contain one.
+fn parse_pdb70_record(data: &[u8]) -> Option<PdbIdentity> {
+    let start = data.windows(4).position(|window| window == PDB70_SIGNATURE)?;
+    let record = &data[start + 4..];
+    if record.len() < 20 {
+        return None;
+    }
+
+    let guid = &record[..16];
+    let age = u32::from_le_bytes(record[16..20].try_into().ok()?);
+    let name_end = record[20..].iter().position(|&byte| byte == 0)?;
+    let name = std::str::from_utf8(&record[20..20 + name_end]).ok()?;
+    if name.is_empty() || !name.is_ascii() {
+        return None;
+    }
+
+    let guid_hex: String = guid.iter().map(|byte| format!("{byte:02X}")).collect();
+    Some(PdbIdentity { name: name.to_string(), guid_hex, age })
+}
+
+/// Downloads and converts `pdb` into an ISF pack, caching it as
+/// `<pdb-name>_<GUID><AGE>.json.xz` in the cache directory (the last entry
+/// of `config.symbol_paths`).
+///
+/// A Windows kernel PDB isn't looked up by banner string against a
+/// `banners.json` index the way Volatility3's Linux/Mac symbol packs are
+/// -- it's located by its own GUID against a Microsoft-compatible symbol
+/// server and converted to ISF, the same pipeline Volatility3's own
+/// `pdbconv` module (and the Windows PDB-scanning automagic) drives.
+fn download_symbol_pack(py: Python<'_>, pdb: &PdbIdentity, config: &SymbolConfig) -> Result<()> {
+    let cache_dir = config
+        .symbol_paths
+        .last()
+        .cloned()
+        .unwrap_or_else(|| PathBuf::from("."));
+    std::fs::create_dir_all(&cache_dir)?;
+
+    let missing = || Error::MissingSymbols {
+        banner: pdb.banner(),
+        candidates: config.server_urls.clone(),
+    };
+
+    let pdbconv = py.import("volatility3.framework.symbols.windows.pdbconv")?;
+    let retriever = pdbconv.getattr("PdbRetreiver")?.call0()?;
+    let guid_age = format!("{}{:X}", pdb.guid_hex, pdb.age);
+    let pdb_path: Option<String> = retriever
+        .call_method1("retreive_pdb", (&pdb.name, &guid_age))?
+        .extract()?;
+    let Some(pdb_path) = pdb_path else {
+        return Err(missing());
+    };
+
+    let isf = pdbconv
+        .getattr("PdbReader")?
+        .call1((&pdb_path,))?
+        .call_method0("get_json")?;
+    let json_text: String = py.import("json")?.call_method1("dumps", (&isf,))?.extract()?;
+    let compressed: Vec<u8> = py
+        .import("lzma")?
+        .call_method1("compress", (json_text.as_bytes(),))?
+        .extract()?;
+
+    let file_name = format!("{}_{}{:X}.json.xz", pdb.name, pdb.guid_hex, pdb.age);
+    std::fs::write(cache_dir.join(file_name), compressed)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pdb70_record(guid: [u8; 16], age: u32, name: &str) -> Vec<u8> {
+        let mut data = PDB70_SIGNATURE.to_vec();
+        data.extend_from_slice(&guid);
+        data.extend_from_slice(&age.to_le_bytes());
+        data.extend_from_slice(name.as_bytes());
+        data.push(0);
+        data
+    }
+
+    #[test]
+    fn parses_identity_from_pdb70_record() {
+        let guid = [
+            0x38, 0x44, 0xDB, 0xB9, 0x20, 0x17, 0x49, 0x67, 0xBE, 0x7A, 0xA4, 0xA9, 0x6E, 0x1C,
+            0x33, 0x17,
+        ];
+        let data = pdb70_record(guid, 0xE, "ntkrnlmp.pdb");
+
+        let pdb = parse_pdb70_record(&data).expect("record should parse");
+
+        assert_eq!(pdb.name, "ntkrnlmp.pdb");
+        assert_eq!(pdb.guid_hex, "3844DBB920174967BE7AA4A96E1C3317");
+        assert_eq!(pdb.age, 0xE);
+        assert_eq!(pdb.banner(), "ntkrnlmp.pdb/3844DBB920174967BE7AA4A96E1C3317E");
+    }
+
+    #[test]
+    fn finds_record_after_leading_noise() {
+        let mut data = vec![0u8; 37];
+        data.extend(pdb70_record([0u8; 16], 1, "x.pdb"));
+
+        let pdb = parse_pdb70_record(&data).expect("record should parse");
+        assert_eq!(pdb.banner(), format!("x.pdb/{}1", "0".repeat(32)));
+    }
+
+    #[test]
+    fn no_signature_returns_none() {
+        assert_eq!(parse_pdb70_record(b"no pdb record in here"), None);
+    }
+
+    #[test]
+    fn truncated_record_returns_none() {
+        let mut data = PDB70_SIGNATURE.to_vec();
+        data.extend_from_slice(&[0u8; 5]);
+        assert_eq!(parse_pdb70_record(&data), None);
+    }
+
+    #[test]
+    fn empty_name_returns_none() {
+        let data = pdb70_record([0u8; 16], 0, "");
+        assert_eq!(parse_pdb70_record(&data), None);
+    }
+
+    #[test]
+    fn non_ascii_name_returns_none() {
+        let mut data = PDB70_SIGNATURE.to_vec();
+        data.extend_from_slice(&[0u8; 16]);
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&[0xFF, 0xFE, 0x00]);
+        assert_eq!(parse_pdb70_record(&data), None);
+    }
+}